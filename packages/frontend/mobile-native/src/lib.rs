@@ -1,4 +1,11 @@
 use affine_common::hashcash::Stamp;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use rand::RngCore;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+mod crypto;
 
 uniffi::setup_scaffolding!("affine_mobile_native");
 
@@ -7,7 +14,322 @@ pub fn hashcash_mint(resource: String) -> String {
   Stamp::mint(resource, None).format()
 }
 
+/// Upper bound on requested difficulty: a SHA-256 digest is [`MAX_BITS`] bits long, so no
+/// value above that could ever satisfy `leading_zero_bits() >= bits` — the mint loop would
+/// spin forever waiting for a hash that can't occur.
+const MAX_BITS: u32 = 256;
+
+#[uniffi::export]
+pub fn hashcash_mint_with_bits(resource: String, bits: u32) -> Option<String> {
+  if bits > MAX_BITS {
+    return None;
+  }
+  Some(Stamp::mint(resource, Some(bits)).format())
+}
+
+/// Reported periodically to the host while [`hashcash_mint_cancellable`] is running, so the
+/// UI thread is never blocked without feedback. `on_attempt` is called every
+/// [`PROGRESS_INTERVAL`] counters tried; `is_cancelled` is polled at the same cadence and,
+/// once it returns `true`, the mint loop bails out on its next check.
+#[uniffi::export(callback_interface)]
+pub trait HashcashProgress: Send + Sync {
+  fn on_attempt(&self, count: u64);
+  fn is_cancelled(&self) -> bool;
+}
+
+const PROGRESS_INTERVAL: u64 = 4096;
+
+/// Mints a stamp like [`hashcash_mint_with_bits`], but reports attempt counts through
+/// `progress` and stops early if the host cancels, returning `None`. The search runs
+/// synchronously on the calling thread until a stamp is found or cancellation is seen.
+#[uniffi::export]
+pub fn hashcash_mint_cancellable(
+  resource: String,
+  bits: u32,
+  progress: Box<dyn HashcashProgress>,
+) -> Option<String> {
+  if bits > MAX_BITS {
+    return None;
+  }
+
+  let rand_field = random_field();
+  let mut counter: u64 = 0;
+
+  loop {
+    if counter % PROGRESS_INTERVAL == 0 {
+      progress.on_attempt(counter);
+      if progress.is_cancelled() {
+        return None;
+      }
+    }
+
+    let candidate = Stamp::new(bits, resource.clone(), rand_field.clone(), counter);
+    if candidate.leading_zero_bits() >= bits {
+      return Some(candidate.format());
+    }
+    counter += 1;
+  }
+}
+
+/// Mints a stamp like [`hashcash_mint_with_bits`], but splits the counter search across
+/// `threads` workers, each starting from its own random `rand` field so they never retread
+/// each other's search space. The first worker to find a valid stamp wins; a shared atomic
+/// flag tells the rest to stop. Returns `None` if every worker thread panics or otherwise
+/// fails to join, rather than propagating a panic across the FFI boundary.
+#[uniffi::export]
+pub fn hashcash_mint_parallel(resource: String, bits: u32, threads: u32) -> Option<String> {
+  if bits > MAX_BITS {
+    return None;
+  }
+
+  let found = Arc::new(AtomicBool::new(false));
+
+  std::thread::scope(|scope| {
+    let handles: Vec<_> = (0..clamp_thread_count(threads))
+      .map(|_| {
+        let resource = resource.clone();
+        let found = Arc::clone(&found);
+        scope.spawn(move || {
+          let rand_field = random_field();
+          let mut counter: u64 = 0;
+          loop {
+            if found.load(Ordering::Relaxed) {
+              return None;
+            }
+            let candidate = Stamp::new(bits, resource.clone(), rand_field.clone(), counter);
+            if candidate.leading_zero_bits() >= bits {
+              found.store(true, Ordering::Relaxed);
+              return Some(candidate.format());
+            }
+            counter += 1;
+          }
+        })
+      })
+      .collect();
+
+    handles.into_iter().find_map(|handle| handle.join().ok().flatten())
+  })
+}
+
+/// Re-checks a stamp produced by [`hashcash_mint`] or [`hashcash_mint_with_bits`] without
+/// round-tripping to the server: the resource must match, the date must still be inside the
+/// validity window, the claimed `bits` must meet [`REQUIRED_BITS`], and the stamp must
+/// actually hash to at least that many leading zero bits.
+#[uniffi::export]
+pub fn hashcash_verify(resource: String, stamp: String) -> bool {
+  verify_stamp(&resource, &stamp, REQUIRED_BITS).is_some()
+}
+
+const VALIDITY_DAYS: i64 = 2;
+/// Minimum proof-of-work difficulty [`hashcash_verify`] will accept, matching the server's
+/// default minting difficulty.
+const REQUIRED_BITS: u32 = 20;
+
+// Parsing and hash-counting both live on `Stamp` itself, so verification stays in lockstep
+// with whatever the canonical version-1 wire format is in `affine_common` — this file never
+// re-derives the layout.
+fn verify_stamp(resource: &str, stamp: &str, bits: u32) -> Option<()> {
+  let parsed = Stamp::from_str(stamp).ok()?;
+
+  if parsed.resource != resource {
+    return None;
+  }
+  if parsed.bits < bits {
+    return None;
+  }
+  if !date_in_validity_window(&parsed.date) {
+    return None;
+  }
+  if parsed.leading_zero_bits() < bits {
+    return None;
+  }
+
+  Some(())
+}
+
+fn random_field() -> String {
+  let mut bytes = [0u8; 8];
+  rand::thread_rng().fill_bytes(&mut bytes);
+  BASE64.encode(bytes)
+}
+
+fn date_in_validity_window(date: &str) -> bool {
+  let Ok(parsed) = chrono::NaiveDate::parse_from_str(date, "%y%m%d") else {
+    return false;
+  };
+  let today = chrono::Utc::now().date_naive();
+  (today - parsed).num_days().abs() <= VALIDITY_DAYS
+}
+
+/// Caps a caller-supplied thread count to the machine's actual parallelism, so a bad value
+/// (typo, stray UI wiring, `u32::MAX`) can't spawn an unbounded number of OS threads.
+fn clamp_thread_count(threads: u32) -> u32 {
+  let available = std::thread::available_parallelism().map_or(1, |n| n.get() as u32);
+  threads.clamp(1, available)
+}
+
+/// Routes Rust `tracing` records to the platform's native log (logcat on Android, oslog on
+/// iOS) so mobile developers can see what the native layer is doing instead of it failing
+/// silently. Safe to call more than once; later calls are no-ops.
+#[uniffi::export]
+pub fn init_logging(level: String) {
+  use std::sync::Once;
+  static INIT: Once = Once::new();
+
+  INIT.call_once(|| {
+    let level = level.parse().unwrap_or(tracing::Level::INFO);
+    install_platform_subscriber(level);
+
+    tracing::info!("affine_mobile_native logging initialized at {level}");
+    #[cfg(debug_assertions)]
+    tracing::warn!("debug build: hashcash minting and other native calls are substantially slower than release");
+  });
+}
+
+// `try_init` rather than `init`: another native module embedded in the same host process may
+// have already installed a global subscriber, and `init_logging` promises to be a no-op in
+// that case rather than panicking.
+
+#[cfg(target_os = "android")]
+fn install_platform_subscriber(level: tracing::Level) {
+  let _ = tracing_subscriber::fmt()
+    .with_max_level(level)
+    .with_writer(paranoid_android::AndroidLogMakeWriter::new("affine_mobile_native".to_string()))
+    .try_init();
+}
+
+#[cfg(target_os = "ios")]
+fn install_platform_subscriber(level: tracing::Level) {
+  use tracing_subscriber::prelude::*;
+  let _ = tracing_subscriber::registry()
+    .with(tracing_subscriber::filter::LevelFilter::from_level(level))
+    .with(tracing_oslog::OsLogger::new("app.affine.pro", "affine_mobile_native"))
+    .try_init();
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn install_platform_subscriber(level: tracing::Level) {
+  let _ = tracing_subscriber::fmt().with_max_level(level).try_init();
+}
+
 #[no_mangle]
 pub extern "C" fn Java_app_affine_pro_MainActivity_hello() -> i32 {
   100
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn verify_accepts_a_freshly_minted_stamp() {
+    let stamp = Stamp::mint("doc:123".to_string(), Some(REQUIRED_BITS)).format();
+    assert!(hashcash_verify("doc:123".to_string(), stamp));
+  }
+
+  #[test]
+  fn verify_rejects_wrong_resource() {
+    let stamp = Stamp::mint("doc:123".to_string(), Some(REQUIRED_BITS)).format();
+    assert!(!hashcash_verify("doc:456".to_string(), stamp));
+  }
+
+  #[test]
+  fn verify_rejects_insufficient_bits() {
+    let stamp = Stamp::mint("doc:123".to_string(), Some(REQUIRED_BITS - 1)).format();
+    assert!(!hashcash_verify("doc:123".to_string(), stamp));
+  }
+
+  #[test]
+  fn verify_rejects_malformed_stamp() {
+    assert!(!hashcash_verify("doc:123".to_string(), "not-a-stamp".to_string()));
+  }
+
+  #[test]
+  fn verify_rejects_tampered_stamp() {
+    let mut stamp = Stamp::mint("doc:123".to_string(), Some(REQUIRED_BITS)).format();
+    stamp.push('0');
+    assert!(!hashcash_verify("doc:123".to_string(), stamp));
+  }
+
+  #[test]
+  fn date_window_accepts_today() {
+    let today = chrono::Utc::now().format("%y%m%d").to_string();
+    assert!(date_in_validity_window(&today));
+  }
+
+  #[test]
+  fn date_window_rejects_expired_date() {
+    let expired = (chrono::Utc::now().date_naive() - chrono::Duration::days(VALIDITY_DAYS + 1))
+      .format("%y%m%d")
+      .to_string();
+    assert!(!date_in_validity_window(&expired));
+  }
+
+  #[test]
+  fn date_window_rejects_garbage() {
+    assert!(!date_in_validity_window("not-a-date"));
+  }
+
+  #[test]
+  fn thread_count_is_clamped_to_available_parallelism() {
+    let available = std::thread::available_parallelism().map_or(1, |n| n.get() as u32);
+    assert_eq!(clamp_thread_count(u32::MAX), available);
+    assert_eq!(clamp_thread_count(0), 1);
+  }
+
+  // Kept low so these tests finish quickly; `verify_stamp` is called directly with the same
+  // bits rather than through `hashcash_verify`, which always requires `REQUIRED_BITS`.
+  const TEST_BITS: u32 = 8;
+
+  struct NeverCancel;
+  impl HashcashProgress for NeverCancel {
+    fn on_attempt(&self, _count: u64) {}
+    fn is_cancelled(&self) -> bool {
+      false
+    }
+  }
+
+  struct AlwaysCancel;
+  impl HashcashProgress for AlwaysCancel {
+    fn on_attempt(&self, _count: u64) {}
+    fn is_cancelled(&self) -> bool {
+      true
+    }
+  }
+
+  #[test]
+  fn mint_cancellable_returns_a_valid_stamp_when_not_cancelled() {
+    let stamp =
+      hashcash_mint_cancellable("doc:123".to_string(), TEST_BITS, Box::new(NeverCancel)).unwrap();
+    assert!(verify_stamp("doc:123", &stamp, TEST_BITS).is_some());
+  }
+
+  #[test]
+  fn mint_cancellable_stops_and_returns_none_when_cancelled() {
+    let result = hashcash_mint_cancellable("doc:123".to_string(), TEST_BITS, Box::new(AlwaysCancel));
+    assert!(result.is_none());
+  }
+
+  #[test]
+  fn mint_cancellable_rejects_bits_above_digest_length() {
+    let result =
+      hashcash_mint_cancellable("doc:123".to_string(), MAX_BITS + 1, Box::new(NeverCancel));
+    assert!(result.is_none());
+  }
+
+  #[test]
+  fn mint_with_bits_rejects_bits_above_digest_length() {
+    assert!(hashcash_mint_with_bits("doc:123".to_string(), MAX_BITS + 1).is_none());
+  }
+
+  #[test]
+  fn mint_parallel_returns_a_valid_stamp_that_round_trips_through_verify() {
+    let stamp = hashcash_mint_parallel("doc:123".to_string(), TEST_BITS, 4).unwrap();
+    assert!(verify_stamp("doc:123", &stamp, TEST_BITS).is_some());
+  }
+
+  #[test]
+  fn mint_parallel_rejects_bits_above_digest_length() {
+    assert!(hashcash_mint_parallel("doc:123".to_string(), MAX_BITS + 1, 4).is_none());
+  }
+}